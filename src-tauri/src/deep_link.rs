@@ -0,0 +1,144 @@
+//! Parses `promptvault://` URLs into a typed [`DeepLinkAction`] and builds
+//! canonical URLs back out of one, so both directions share the same
+//! percent-encoding and validation logic.
+
+use std::collections::HashMap;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+const SCHEME: &str = "promptvault://";
+
+/// Characters percent-encoded when building a deep link URL, on top of the
+/// control characters `percent_encoding::CONTROLS` already covers.
+const QUERY_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'/')
+    .add(b':')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>')
+    .add(b'?')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// A validated, typed representation of a `promptvault://` deep link.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum DeepLinkAction {
+    Open { id: String },
+    Run { id: String, args: HashMap<String, String> },
+    Import { url: String },
+}
+
+/// Why a `promptvault://` URL could not be parsed into a [`DeepLinkAction`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLinkError {
+    MissingScheme,
+    UnknownAction(String),
+    MissingField(&'static str),
+}
+
+impl std::fmt::Display for DeepLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeepLinkError::MissingScheme => write!(f, "URL does not start with {SCHEME}"),
+            DeepLinkError::UnknownAction(action) => write!(f, "unknown deep link action '{action}'"),
+            DeepLinkError::MissingField(field) => write!(f, "missing required field '{field}'"),
+        }
+    }
+}
+
+impl std::error::Error for DeepLinkError {}
+
+impl DeepLinkAction {
+    /// Builds an action from an action name and its already-decoded fields,
+    /// the shared core of [`parse_deep_link`] and `create_share_link`.
+    fn from_parts(action: &str, mut fields: HashMap<String, String>) -> Result<Self, DeepLinkError> {
+        match action {
+            "open" => Ok(DeepLinkAction::Open {
+                id: fields.remove("id").ok_or(DeepLinkError::MissingField("id"))?,
+            }),
+            "run" => {
+                let id = fields.remove("id").ok_or(DeepLinkError::MissingField("id"))?;
+                let args = fields
+                    .into_iter()
+                    .filter_map(|(key, value)| key.strip_prefix("arg.").map(|name| (name.to_string(), value)))
+                    .collect();
+                Ok(DeepLinkAction::Run { id, args })
+            }
+            "import" => Ok(DeepLinkAction::Import {
+                url: fields.remove("url").ok_or(DeepLinkError::MissingField("url"))?,
+            }),
+            other => Err(DeepLinkError::UnknownAction(other.to_string())),
+        }
+    }
+}
+
+/// Parses a `promptvault://<action>?key=value&...` URL into a typed
+/// [`DeepLinkAction`], percent-decoding every query key and value.
+pub fn parse_deep_link(url: &str) -> Result<DeepLinkAction, DeepLinkError> {
+    let rest = url.strip_prefix(SCHEME).ok_or(DeepLinkError::MissingScheme)?;
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let action = action.trim_end_matches('/');
+
+    let mut fields: HashMap<String, String> = HashMap::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = percent_decode_str(key).decode_utf8_lossy().into_owned();
+        let value = percent_decode_str(value).decode_utf8_lossy().into_owned();
+        fields.insert(key, value);
+    }
+
+    DeepLinkAction::from_parts(action, fields)
+}
+
+/// Builds a [`DeepLinkAction`] directly from an action name and raw argument
+/// map, as used by the `create_share_link` command. Unlike [`parse_deep_link`]
+/// the values are taken as-is since they never passed through a URL, and
+/// `run` arguments are bare keys (`{"id": .., "foo": "bar"}`) rather than the
+/// `arg.`-prefixed query keys `from_parts` expects from a parsed URL.
+pub fn action_from_command_args(action: &str, mut args: HashMap<String, String>) -> Result<DeepLinkAction, DeepLinkError> {
+    match action {
+        "run" => {
+            let id = args.remove("id").ok_or(DeepLinkError::MissingField("id"))?;
+            Ok(DeepLinkAction::Run { id, args })
+        }
+        _ => DeepLinkAction::from_parts(action, args),
+    }
+}
+
+/// Builds a canonical `promptvault://` URL for `action`, percent-encoding
+/// every argument value. The inverse of [`parse_deep_link`].
+pub fn build_deep_link_url(action: &DeepLinkAction) -> String {
+    fn encode(value: &str) -> String {
+        utf8_percent_encode(value, QUERY_ENCODE_SET).to_string()
+    }
+
+    match action {
+        DeepLinkAction::Open { id } => format!("{SCHEME}open?id={}", encode(id)),
+        DeepLinkAction::Run { id, args } => {
+            let mut url = format!("{SCHEME}run?id={}", encode(id));
+            // Sort by key so the same action always yields the same URL
+            // string, regardless of the arbitrary HashMap iteration order.
+            let mut sorted_args: Vec<_> = args.iter().collect();
+            sorted_args.sort_by_key(|(name, _)| *name);
+            for (name, value) in sorted_args {
+                url.push_str(&format!("&arg.{}={}", encode(name), encode(value)));
+            }
+            url
+        }
+        DeepLinkAction::Import { url } => format!("{SCHEME}import?url={}", encode(url)),
+    }
+}
@@ -0,0 +1,88 @@
+//! Validates a parsed [`DeepLinkAction`] before it is stored or emitted to
+//! the webview, so a hostile `promptvault://` URL can't reach privileged
+//! commands with oversized arguments or a path that escapes the vault.
+
+use std::path::{Path, PathBuf};
+
+use crate::deep_link::DeepLinkAction;
+
+/// Maximum length, in bytes, allowed for any single decoded argument value.
+const MAX_ARG_LEN: usize = 2048;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    ArgTooLong { field: String, len: usize },
+    PathEscapesVault { path: String },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::ArgTooLong { field, len } => {
+                write!(f, "field '{field}' is {len} bytes, exceeding the {MAX_ARG_LEN} byte limit")
+            }
+            ValidationError::PathEscapesVault { path } => {
+                write!(f, "path '{path}' resolves outside the vault directory")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks `action` against the actions the parser already allowlists, plus
+/// argument-length and vault-path constraints not enforced by parsing alone.
+pub fn validate_action(action: &DeepLinkAction, vault_dir: &Path) -> Result<(), ValidationError> {
+    match action {
+        DeepLinkAction::Open { id } => check_len("id", id),
+        DeepLinkAction::Run { id, args } => {
+            check_len("id", id)?;
+            for (name, value) in args {
+                check_len(&format!("arg.{name}"), value)?;
+            }
+            Ok(())
+        }
+        DeepLinkAction::Import { url } => {
+            check_len("url", url)?;
+            check_import_path(url, vault_dir)
+        }
+    }
+}
+
+fn check_len(field: &str, value: &str) -> Result<(), ValidationError> {
+    if value.len() > MAX_ARG_LEN {
+        return Err(ValidationError::ArgTooLong { field: field.to_string(), len: value.len() });
+    }
+    Ok(())
+}
+
+/// Remote imports are fetched over the network, not read from disk, so only
+/// local paths need to be confined to the vault directory.
+fn check_import_path(url: &str, vault_dir: &Path) -> Result<(), ValidationError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return Ok(());
+    }
+
+    let raw_path = url.strip_prefix("file://").unwrap_or(url);
+    let candidate = PathBuf::from(raw_path);
+
+    // Reject `..` lexically up front: `canonicalize` returns `Err` for a path
+    // that doesn't exist yet (e.g. an import target that hasn't been written),
+    // and falling back to the un-normalized candidate in that case would let
+    // `vault_dir.join("../../../etc/evil")` pass the `starts_with` check below
+    // purely because it's textually prefixed by `vault_dir`.
+    if candidate.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        return Err(ValidationError::PathEscapesVault { path: url.to_string() });
+    }
+
+    let candidate = if candidate.is_absolute() { candidate } else { vault_dir.join(candidate) };
+
+    let resolved = candidate.canonicalize().unwrap_or(candidate);
+    let vault_dir = vault_dir.canonicalize().unwrap_or_else(|_| vault_dir.to_path_buf());
+
+    if resolved.starts_with(&vault_dir) {
+        Ok(())
+    } else {
+        Err(ValidationError::PathEscapesVault { path: url.to_string() })
+    }
+}
@@ -0,0 +1,52 @@
+//! Funnels every deep link entry point (single-instance, `on_open_url`,
+//! cold-start args) through one queue, so a link that arrives before the
+//! frontend calls `frontend_ready` is never lost and is never guessed at
+//! with timed retries.
+
+use std::sync::Mutex;
+
+use crate::deep_link::DeepLinkAction;
+
+#[derive(Default)]
+struct QueueState {
+    pending: Vec<DeepLinkAction>,
+    frontend_is_ready: bool,
+}
+
+#[derive(Default)]
+pub struct DeepLinkQueue {
+    state: Mutex<QueueState>,
+}
+
+impl DeepLinkQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submits `action` from a deep link entry point. Returns `Some(action)`
+    /// when the frontend is already ready, so the caller can emit it
+    /// directly; otherwise the action is queued and `None` is returned.
+    ///
+    /// The ready check and the enqueue both happen under the same lock as
+    /// `drain`'s flip-and-take, so a `submit` racing a `frontend_ready` call
+    /// can never observe "not ready" and then enqueue after the drain has
+    /// already run — which would otherwise strand the action in `pending`
+    /// with nothing left to drain it.
+    pub fn submit(&self, action: DeepLinkAction) -> Option<DeepLinkAction> {
+        let mut state = self.state.lock().unwrap();
+        if state.frontend_is_ready {
+            Some(action)
+        } else {
+            state.pending.push(action);
+            None
+        }
+    }
+
+    /// Called by the `frontend_ready` command: marks the frontend ready for
+    /// direct emission going forward and drains everything queued so far.
+    pub fn drain(&self) -> Vec<DeepLinkAction> {
+        let mut state = self.state.lock().unwrap();
+        state.frontend_is_ready = true;
+        std::mem::take(&mut state.pending)
+    }
+}
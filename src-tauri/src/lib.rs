@@ -1,61 +1,83 @@
-use std::sync::Mutex;
+mod deep_link;
+mod deep_link_guard;
+mod deep_link_queue;
+#[cfg(target_os = "linux")]
+mod linux_desktop;
+mod vault_watcher;
+
+use std::collections::HashMap;
+
+use deep_link_queue::DeepLinkQueue;
 use tauri::{Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
 
-// State to hold pending deep link URL until frontend is ready
-struct PendingDeepLink(Mutex<Option<String>>);
-
-// Command for frontend to signal it's ready and get any pending deep link
+// Command for frontend to signal it's ready: drains anything that arrived
+// before this call and switches the queue to direct emission from here on.
 #[tauri::command]
-fn frontend_ready(state: tauri::State<PendingDeepLink>) -> Option<String> {
-    log::info!("[DeepLink] Frontend ready command called");
-    if let Ok(mut pending) = state.0.lock() {
-        let url = pending.take();
-        if let Some(ref url_str) = url {
-            log::info!("[DeepLink] Returning pending URL: {}", url_str);
-        } else {
-            log::info!("[DeepLink] No pending URL to return");
+fn frontend_ready(state: tauri::State<DeepLinkQueue>) -> Vec<deep_link::DeepLinkAction> {
+    let drained = state.drain();
+    log::info!("[DeepLink] Frontend ready, draining {} queued action(s)", drained.len());
+    drained
+}
+
+// Parses, validates, and either emits `url` directly (frontend already ready)
+// or enqueues it on `queue` for the next `frontend_ready` drain. Shared by
+// every deep link entry point: single-instance, cold-start args, `on_open_url`.
+fn handle_deep_link<R: tauri::Runtime>(app: &impl Manager<R>, queue: &DeepLinkQueue, url: &str, source: &str) {
+    let action = match deep_link::parse_deep_link(url) {
+        Ok(action) => action,
+        Err(e) => {
+            log::info!("[DeepLink] Ignoring unparseable {} URL '{}': {}", source, url, e);
+            return;
+        }
+    };
+
+    if let Err(e) = deep_link_guard::validate_action(&action, &vault_dir(app)) {
+        log::warn!("[DeepLink] Dropping {} URL that failed validation: {}", source, e);
+        return;
+    }
+
+    match queue.submit(action) {
+        Some(action) => {
+            log::info!("[DeepLink] Emitting {} deep link directly: {:?}", source, action);
+            let _ = app.emit("deep-link", &action);
+        }
+        None => {
+            log::info!("[DeepLink] Frontend not ready yet, queued {} deep link", source);
         }
-        url
-    } else {
-        log::error!("[DeepLink] Failed to lock pending state");
-        None
     }
 }
 
+// Builds a shareable promptvault:// link for `action`/`args`, copies it to the
+// clipboard, and returns it so the UI can confirm what was copied.
+#[tauri::command]
+fn create_share_link(app: tauri::AppHandle, action: String, args: HashMap<String, String>) -> Result<String, String> {
+    let action = deep_link::action_from_command_args(&action, args).map_err(|e| e.to_string())?;
+    let url = deep_link::build_deep_link_url(&action);
+
+    app.clipboard().write_text(url.clone()).map_err(|e| e.to_string())?;
+    log::info!("[DeepLink] Copied share link to clipboard: {}", url);
+
+    Ok(url)
+}
+
+// The directory untrusted `import` deep links are confined to.
+fn vault_dir<R: tauri::Runtime>(app: &impl Manager<R>) -> std::path::PathBuf {
+    app.path().app_data_dir().unwrap_or_default().join("vault")
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .manage(PendingDeepLink(Mutex::new(None)))
-        .invoke_handler(tauri::generate_handler![frontend_ready])
+        .manage(DeepLinkQueue::new())
+        .invoke_handler(tauri::generate_handler![frontend_ready, create_share_link])
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
             // Forward deep link args to running instance
             if let Some(url) = args.get(1) {
                 log::info!("[SingleInstance] Found arg: {}", url);
-                if url.starts_with("promptvault://") {
-                    log::info!("[SingleInstance] Forwarding deep link: {}", url);
-                    
-                    // Try multiple times with delays to ensure frontend receives the event
-                    let app_clone = app.clone();
-                    let url_clone = url.clone();
-                    
-                    // Immediate emit
-                    let _ = app.emit("deep-link", url.clone());
-                    
-                    // Delayed emit attempts
-                    std::thread::spawn(move || {
-                        std::thread::sleep(std::time::Duration::from_millis(500));
-                        log::info!("[SingleInstance] Emitting deep-link event (500ms delay): {}", url_clone);
-                        let _ = app_clone.emit("deep-link", url_clone.clone());
-                        
-                        std::thread::sleep(std::time::Duration::from_millis(1000));
-                        log::info!("[SingleInstance] Emitting deep-link event (1500ms delay): {}", url_clone);
-                        let _ = app_clone.emit("deep-link", url_clone);
-                    });
-                } else {
-                    log::info!("[SingleInstance] Ignoring non-deep-link arg: {}", url);
-                }
+                handle_deep_link(app, &app.state::<DeepLinkQueue>(), url, "single-instance");
             } else {
                 log::info!("[SingleInstance] No arguments found");
             }
@@ -80,6 +102,11 @@ pub fn run() {
                 }
             }
 
+            // Register as the system's promptvault:// handler (needed for cold-start
+            // deep links on Linux, which otherwise never reach this binary's argv)
+            #[cfg(target_os = "linux")]
+            linux_desktop::register();
+
             // Additional development mode setup
             #[cfg(debug_assertions)]
             {
@@ -92,23 +119,14 @@ pub fn run() {
                 }
             }
 
-            // Check for cold-start deep link from CLI args (Linux/Windows)
-            // Store in state - will be returned when frontend calls frontend_ready command
+            // Check for cold-start deep link from CLI args (Linux/Windows).
+            // If the frontend isn't ready yet this just queues it for the
+            // next `frontend_ready` drain, same as the other entry points.
             let args: Vec<String> = std::env::args().collect();
             log::info!("[DeepLink] CLI args: {:?}", args);
-            if let Some(url) = args.get(1).cloned() {
+            if let Some(url) = args.get(1) {
                 log::info!("[DeepLink] Found URL arg: {}", url);
-                if url.starts_with("promptvault://") {
-                    log::info!("[DeepLink] Cold start with URL, storing for later: {}", url);
-                    if let Ok(mut pending) = app.state::<PendingDeepLink>().0.lock() {
-                        *pending = Some(url.clone());
-                        log::info!("[DeepLink] Successfully stored pending URL: {}", url);
-                    } else {
-                        log::error!("[DeepLink] Failed to lock pending state");
-                    }
-                } else {
-                    log::info!("[DeepLink] URL does not start with promptvault://, ignoring");
-                }
+                handle_deep_link(app, &app.state::<DeepLinkQueue>(), url, "cold-start");
             } else {
                 log::info!("[DeepLink] No URL argument found");
             }
@@ -118,9 +136,14 @@ pub fn run() {
             app.deep_link().on_open_url(move |event| {
                 if let Some(url) = event.urls().first() {
                     log::info!("[DeepLink] onOpenUrl: {}", url);
-                    let _ = handle.emit("deep-link", url.to_string());
+                    handle_deep_link(&handle, &handle.state::<DeepLinkQueue>(), url.as_str(), "onOpenUrl");
                 }
             });
+
+            // Watch the vault for external edits (e.g. another editor, or a git
+            // sync) and notify the webview so it can refresh without polling
+            // from the frontend itself.
+            vault_watcher::spawn(app.handle().clone(), vault_dir(app));
             // Setup logging in debug mode
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -0,0 +1,94 @@
+//! Registers the `promptvault` URL scheme with the desktop's MIME database on
+//! Linux. Unlike macOS/Windows, Linux only routes `promptvault://` URLs to
+//! this binary if a `.desktop` entry declares `x-scheme-handler/promptvault`
+//! and `xdg-mime` has been told to use it as the default handler.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+const DESKTOP_FILE_NAME: &str = "promptvault-url-handler.desktop";
+const APP_NAME: &str = "Pocket Prompt";
+
+/// Writes (or updates) the `.desktop` entry for this binary and registers it
+/// as the `promptvault://` handler via `xdg-mime` / `update-desktop-database`.
+/// Failures are logged and otherwise ignored, the same way `register_all()`
+/// degrades gracefully elsewhere in `setup` — deep links just won't route
+/// until the user reinstalls or re-runs the app with the tools available.
+pub fn register() {
+    let exe = match std::env::current_exe() {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[LinuxDesktop] Failed to resolve current executable: {:?}", e);
+            return;
+        }
+    };
+
+    let desktop_file = match write_desktop_file(&exe) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[LinuxDesktop] Failed to write .desktop file: {:?}", e);
+            return;
+        }
+    };
+
+    run_logged("xdg-mime", &["default", DESKTOP_FILE_NAME, "x-scheme-handler/promptvault"]);
+    run_logged(
+        "update-desktop-database",
+        &[applications_dir().to_string_lossy().as_ref()],
+    );
+
+    log::info!(
+        "[LinuxDesktop] Registered {} as the promptvault:// handler",
+        desktop_file.display()
+    );
+}
+
+fn applications_dir() -> PathBuf {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default().join(".local/share"));
+    data_home.join("applications")
+}
+
+fn write_desktop_file(exe: &std::path::Path) -> io::Result<PathBuf> {
+    let dir = applications_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(DESKTOP_FILE_NAME);
+
+    let contents = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={APP_NAME}\n\
+         Exec={} %u\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/promptvault;\n",
+        quote_exec_path(exe)
+    );
+
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Quotes an executable path for the `Exec=` line per the Desktop Entry
+/// Specification, so install paths containing spaces (e.g. `My Apps/`)
+/// still produce a valid, launchable entry.
+fn quote_exec_path(exe: &std::path::Path) -> String {
+    let escaped = exe.display().to_string().replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+fn run_logged(program: &str, args: &[&str]) {
+    match Command::new(program).args(args).status() {
+        Ok(status) if status.success() => {
+            log::info!("[LinuxDesktop] `{} {}` succeeded", program, args.join(" "));
+        }
+        Ok(status) => {
+            log::warn!("[LinuxDesktop] `{} {}` exited with {}", program, args.join(" "), status);
+        }
+        Err(e) => {
+            log::warn!("[LinuxDesktop] Failed to run `{}`: {:?}", program, e);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Polls the on-disk prompt vault off the main thread for changes made
+//! outside the app (e.g. an external editor, or prompts synced in via git)
+//! and emits `vault-changed` so the webview can refresh. Polling happens on
+//! its own thread so it never blocks the global-shortcut toggle or IPC.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Emitter};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+/// Spawns a background thread that polls `vault_dir` every [`POLL_INTERVAL`]
+/// and emits `vault-changed` on `app` whenever a prompt file is added,
+/// removed, or its modification time changes since the last poll.
+pub fn spawn(app: AppHandle, vault_dir: PathBuf) {
+    std::thread::spawn(move || {
+        let mut snapshot = scan(&vault_dir, true);
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = scan(&vault_dir, false);
+            if current != snapshot {
+                log::info!("[VaultWatcher] Detected vault change under {}", vault_dir.display());
+                let _ = app.emit("vault-changed", ());
+                snapshot = current;
+            }
+        }
+    });
+}
+
+// `warn_if_missing` keeps a missing vault dir from flooding the log on every
+// poll: only the very first scan passes `true`, so a missing dir is logged
+// once at startup. The poll loop always passes `false`, so it stays quiet on
+// every later poll regardless of whether the dir reappears and vanishes
+// again in between.
+//
+// Walks `vault_dir` recursively: prompts may be organized into
+// subdirectories, and a non-recursive `read_dir` would silently stop
+// watching anything not directly at the top level.
+fn scan(vault_dir: &Path, warn_if_missing: bool) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut dirs = vec![vault_dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                if warn_if_missing && dir == vault_dir {
+                    log::warn!("[VaultWatcher] Failed to read vault dir {}: {:?}", dir.display(), e);
+                }
+                continue;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+
+    snapshot
+}